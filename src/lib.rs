@@ -3,8 +3,20 @@
 //! This crate provides:
 //!
 //! - A `Mutex` trait that is to be used as the foundation of exclusive access to the data
-//! contained within it
+//!   contained within it
+//! - A `RwLock` trait for shared (read) and exclusive (write) access to the data contained
+//!   within it, for locks that can tell the two apart
+//! - A `SharedMutex` trait for locks that grant exclusive access through a shared `&self`
+//!   reference, using interior mutability so the same lock can be shared behind several `&`
+//!   references and locked from multiple call sites, plus a `Shared` wrapper (coherence rules
+//!   out a blanket impl, so callers must opt in with `Shared(&lock)`) that adapts any
+//!   `SharedMutex` to the `Mutex` interface
 //! - Helper traits and implementations which allows for multiple locks to be taken at once
+//! - Opt-in `Mutex` impls for common ecosystem lock types, enabled through the `std`,
+//!   `critical-section` and `spin` Cargo features
+//! - An opt-in `MutexGuard` trait adding a `lock_guard` RAII alternative to the closure-based
+//!   `lock`, plus [`map`] and [`map_owned`] functions to project a guard onto a sub-field, or an
+//!   owned value computed from its data, while keeping the lock held
 //!
 //! # Example
 //!
@@ -34,7 +46,8 @@
 //!
 //! # Minimum Supported Rust Version (MSRV)
 //!
-//! This crate is guaranteed to compile on stable Rust 1.31 and up. It *might*
+//! This crate is guaranteed to compile on stable Rust 1.75 and up, since `MutexGuard::lock_guard`
+//! and `SharedMutex::lock_guard` rely on return-position `impl Trait` in traits. It *might*
 //! compile with older versions but that may change in any new patch release.
 
 #![no_std]
@@ -86,6 +99,38 @@ pub mod prelude {
     //!     });
     //! }
     //! ```
+    //!
+    //! # `RwLock` tuples
+    //!
+    //! The same left-to-right tuple helpers are provided for [`RwLock`](../trait.RwLock.html),
+    //! split into a read-only flavor (`ReadTupleExtNN`) and a write flavor (`WriteTupleExtNN`) so
+    //! that generic code can ask for "N things I only read" separately from "N things I mutate".
+    //!
+    //! ```
+    //! use mutex_trait::*;
+    //!
+    //! fn tuple_read(a: &mut impl RwLock<Data = i32>, b: &mut impl RwLock<Data = i32>) -> i32 {
+    //!     (a, b).read(|a, b| *a + *b)
+    //! }
+    //! ```
+    //!
+    //! # `SharedMutex` tuples
+    //!
+    //! The same left-to-right tuple helper is provided for
+    //! [`SharedMutex`](../trait.SharedMutex.html), locking every element of the tuple through a
+    //! shared `&self` reference.
+    //!
+    //! ```
+    //! use mutex_trait::*;
+    //! use core::cell::RefCell;
+    //!
+    //! fn shared_lock(a: &RefCell<i32>, b: &RefCell<i32>) {
+    //!     (a, b).lock(|a, b| {
+    //!         *a += 1;
+    //!         *b += 1;
+    //!     });
+    //! }
+    //! ```
 
     macro_rules! lock {
         ($e:ident, $fun:block) => {
@@ -96,6 +141,15 @@ pub mod prelude {
         };
     }
 
+    macro_rules! try_lock {
+        ($e:ident, $fun:block) => {
+            $e.try_lock(|$e| $fun )
+        };
+        ($e:ident, $($es:ident),+, $fun:block) => {
+            $e.try_lock(|$e| try_lock!($($es),*, $fun)).and_then(|r| r)
+        };
+    }
+
     macro_rules! make_tuple_impl {
         ($name:ident, $($es:ident),+) => {
             /// Auto-generated tuple implementation, see [Mutex](../trait.Mutex.html) for details
@@ -107,6 +161,11 @@ pub mod prelude {
 
                 /// Creates a critical section and grants temporary access to the protected data
                 fn lock<R>(&mut self, f: impl FnOnce($(&mut Self::$es),*) -> R) -> R;
+
+                /// Attempts to lock every element of the tuple left-to-right. If any lock is
+                /// not available, every lock already taken is released and `None` is returned
+                /// without running `f`
+                fn try_lock<R>(&mut self, f: impl FnOnce($(&mut Self::$es),*) -> R) -> Option<R>;
             }
 
             impl<$($es),*> $name for ($($es),*)
@@ -124,6 +183,14 @@ pub mod prelude {
 
                     lock!($($es),*, { f($($es),*) })
                 }
+
+                fn try_lock<R>(&mut self, f: impl FnOnce($(&mut Self::$es),*) -> R) -> Option<R> {
+                    let ($(
+                            $es
+                    ),*) = self;
+
+                    try_lock!($($es),*, { f($($es),*) })
+                }
             }
         };
     }
@@ -146,9 +213,215 @@ pub mod prelude {
     make_tuple_impl!(
         TupleExt16, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16
     );
+
+    // `RwLock` tuples: same left-to-right helpers, split into a read-only flavor
+    // (`ReadTupleExtNN`) and a write flavor (`WriteTupleExtNN`)
+
+    macro_rules! read_lock {
+        ($e:ident, $fun:block) => {
+            $e.read(|$e| $fun )
+        };
+        ($e:ident, $($es:ident),+, $fun:block) => {
+            $e.read(|$e| read_lock!($($es),*, $fun))
+        };
+    }
+
+    macro_rules! write_lock {
+        ($e:ident, $fun:block) => {
+            $e.write(|$e| $fun )
+        };
+        ($e:ident, $($es:ident),+, $fun:block) => {
+            $e.write(|$e| write_lock!($($es),*, $fun))
+        };
+    }
+
+    macro_rules! make_tuple_read_impl {
+        ($name:ident, $($es:ident),+) => {
+            /// Auto-generated tuple implementation, see [RwLock](../trait.RwLock.html) for details
+            pub trait $name {
+                $(
+                    /// Data protected by the lock
+                    type $es;
+                )*
+
+                /// Creates a critical section and grants temporary shared access to the data
+                /// protected by each lock in the tuple, left-to-right
+                fn read<R>(&self, f: impl FnOnce($(&Self::$es),*) -> R) -> R;
+            }
+
+            impl<$($es),*> $name for ($($es),*)
+            where
+                $($es: crate::RwLock),*
+            {
+                $(
+                    type $es = $es::Data;
+                )*
+
+                fn read<R>(&self, f: impl FnOnce($(&Self::$es),*) -> R) -> R {
+                    let ($(
+                            $es
+                    ),*) = self;
+
+                    read_lock!($($es),*, { f($($es),*) })
+                }
+            }
+        };
+    }
+
+    macro_rules! make_tuple_write_impl {
+        ($name:ident, $($es:ident),+) => {
+            /// Auto-generated tuple implementation, see [RwLock](../trait.RwLock.html) for details
+            pub trait $name {
+                $(
+                    /// Data protected by the lock
+                    type $es;
+                )*
+
+                /// Creates a critical section and grants temporary exclusive access to the data
+                /// protected by each lock in the tuple, left-to-right
+                fn write<R>(&mut self, f: impl FnOnce($(&mut Self::$es),*) -> R) -> R;
+            }
+
+            impl<$($es),*> $name for ($($es),*)
+            where
+                $($es: crate::RwLock),*
+            {
+                $(
+                    type $es = $es::Data;
+                )*
+
+                fn write<R>(&mut self, f: impl FnOnce($(&mut Self::$es),*) -> R) -> R {
+                    let ($(
+                            $es
+                    ),*) = self;
+
+                    write_lock!($($es),*, { f($($es),*) })
+                }
+            }
+        };
+    }
+
+    // Generate tuple read-lock impls
+    make_tuple_read_impl!(ReadTupleExt02, T1, T2);
+    make_tuple_read_impl!(ReadTupleExt03, T1, T2, T3);
+    make_tuple_read_impl!(ReadTupleExt04, T1, T2, T3, T4);
+    make_tuple_read_impl!(ReadTupleExt05, T1, T2, T3, T4, T5);
+    make_tuple_read_impl!(ReadTupleExt06, T1, T2, T3, T4, T5, T6);
+    make_tuple_read_impl!(ReadTupleExt07, T1, T2, T3, T4, T5, T6, T7);
+    make_tuple_read_impl!(ReadTupleExt08, T1, T2, T3, T4, T5, T6, T7, T8);
+    make_tuple_read_impl!(ReadTupleExt09, T1, T2, T3, T4, T5, T6, T7, T8, T9);
+    make_tuple_read_impl!(ReadTupleExt10, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+    make_tuple_read_impl!(ReadTupleExt11, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+    make_tuple_read_impl!(ReadTupleExt12, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
+    make_tuple_read_impl!(ReadTupleExt13, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13);
+    make_tuple_read_impl!(
+        ReadTupleExt14, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14
+    );
+    make_tuple_read_impl!(
+        ReadTupleExt15, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15
+    );
+    make_tuple_read_impl!(
+        ReadTupleExt16, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16
+    );
+
+    // Generate tuple write-lock impls
+    make_tuple_write_impl!(WriteTupleExt02, T1, T2);
+    make_tuple_write_impl!(WriteTupleExt03, T1, T2, T3);
+    make_tuple_write_impl!(WriteTupleExt04, T1, T2, T3, T4);
+    make_tuple_write_impl!(WriteTupleExt05, T1, T2, T3, T4, T5);
+    make_tuple_write_impl!(WriteTupleExt06, T1, T2, T3, T4, T5, T6);
+    make_tuple_write_impl!(WriteTupleExt07, T1, T2, T3, T4, T5, T6, T7);
+    make_tuple_write_impl!(WriteTupleExt08, T1, T2, T3, T4, T5, T6, T7, T8);
+    make_tuple_write_impl!(WriteTupleExt09, T1, T2, T3, T4, T5, T6, T7, T8, T9);
+    make_tuple_write_impl!(WriteTupleExt10, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+    make_tuple_write_impl!(WriteTupleExt11, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+    make_tuple_write_impl!(WriteTupleExt12, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
+    make_tuple_write_impl!(
+        WriteTupleExt13, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13
+    );
+    make_tuple_write_impl!(
+        WriteTupleExt14, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14
+    );
+    make_tuple_write_impl!(
+        WriteTupleExt15, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15
+    );
+    make_tuple_write_impl!(
+        WriteTupleExt16, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16
+    );
+
+    // `SharedMutex` tuples: the same left-to-right helper, locking every element of the tuple
+    // through a shared `&self` reference
+
+    macro_rules! shared_lock {
+        ($e:ident, $fun:block) => {
+            $e.lock(|$e| $fun )
+        };
+        ($e:ident, $($es:ident),+, $fun:block) => {
+            $e.lock(|$e| shared_lock!($($es),*, $fun))
+        };
+    }
+
+    macro_rules! make_tuple_shared_impl {
+        ($name:ident, $($es:ident),+) => {
+            /// Auto-generated tuple implementation, see [SharedMutex](../trait.SharedMutex.html)
+            /// for details
+            pub trait $name {
+                $(
+                    /// Data protected by the mutex
+                    type $es;
+                )*
+
+                /// Creates a critical section and grants temporary access to the protected data
+                fn lock<R>(&self, f: impl FnOnce($(&mut Self::$es),*) -> R) -> R;
+            }
+
+            impl<$($es),*> $name for ($($es),*)
+            where
+                $($es: crate::SharedMutex),*
+            {
+                $(
+                    type $es = $es::Data;
+                )*
+
+                fn lock<R>(&self, f: impl FnOnce($(&mut Self::$es),*) -> R) -> R {
+                    let ($(
+                            $es
+                    ),*) = self;
+
+                    shared_lock!($($es),*, { f($($es),*) })
+                }
+            }
+        };
+    }
+
+    // Generate tuple shared-lock impls
+    make_tuple_shared_impl!(SharedTupleExt02, T1, T2);
+    make_tuple_shared_impl!(SharedTupleExt03, T1, T2, T3);
+    make_tuple_shared_impl!(SharedTupleExt04, T1, T2, T3, T4);
+    make_tuple_shared_impl!(SharedTupleExt05, T1, T2, T3, T4, T5);
+    make_tuple_shared_impl!(SharedTupleExt06, T1, T2, T3, T4, T5, T6);
+    make_tuple_shared_impl!(SharedTupleExt07, T1, T2, T3, T4, T5, T6, T7);
+    make_tuple_shared_impl!(SharedTupleExt08, T1, T2, T3, T4, T5, T6, T7, T8);
+    make_tuple_shared_impl!(SharedTupleExt09, T1, T2, T3, T4, T5, T6, T7, T8, T9);
+    make_tuple_shared_impl!(SharedTupleExt10, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10);
+    make_tuple_shared_impl!(SharedTupleExt11, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11);
+    make_tuple_shared_impl!(SharedTupleExt12, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12);
+    make_tuple_shared_impl!(
+        SharedTupleExt13, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13
+    );
+    make_tuple_shared_impl!(
+        SharedTupleExt14, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14
+    );
+    make_tuple_shared_impl!(
+        SharedTupleExt15, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15
+    );
+    make_tuple_shared_impl!(
+        SharedTupleExt16, T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16
+    );
 }
 
 use core::cell::RefCell;
+use core::ops::{Deref, DerefMut};
 pub use prelude::*;
 
 /// Any object implementing this trait guarantees exclusive access to the data contained
@@ -159,9 +432,17 @@ pub trait Mutex {
 
     /// Creates a critical section and grants temporary access to the protected data
     fn lock<R>(&mut self, f: impl FnOnce(&mut Self::Data) -> R) -> R;
+
+    /// Attempts to create a critical section and grants temporary access to the protected data,
+    /// returning `None` instead of blocking if the lock is not available. The default
+    /// implementation is for infallible locks and always succeeds; ticket/spin-style locks
+    /// should override it to report contention.
+    fn try_lock<R>(&mut self, f: impl FnOnce(&mut Self::Data) -> R) -> Option<R> {
+        Some(self.lock(f))
+    }
 }
 
-// `lock` will now work on any mutable reference to a lock
+// `lock`/`try_lock` will now work on any mutable reference to a lock
 impl<L> Mutex for &'_ mut L
 where
     L: Mutex,
@@ -171,6 +452,30 @@ where
     fn lock<R>(&mut self, f: impl FnOnce(&mut Self::Data) -> R) -> R {
         L::lock(self, f)
     }
+
+    fn try_lock<R>(&mut self, f: impl FnOnce(&mut Self::Data) -> R) -> Option<R> {
+        L::try_lock(self, f)
+    }
+}
+
+/// Optional extension of [`Mutex`] for locks that can hand out a scoped RAII guard instead of
+/// taking a closure. Kept separate from `Mutex` so closure-only locks (e.g. `cortex_m`'s
+/// `interrupt::Mutex<RefCell<T>>`, which cannot produce a guard that outlives the critical
+/// section used to take it) can still implement `Mutex` without providing this.
+pub trait MutexGuard: Mutex {
+    /// Locks the mutex and returns a RAII guard granting scoped access to the protected data.
+    /// The lock is released when the guard is dropped.
+    fn lock_guard(&mut self) -> impl StableDeref<Target = Self::Data> + '_;
+}
+
+// `lock_guard` will now work on any mutable reference to a lock
+impl<L> MutexGuard for &'_ mut L
+where
+    L: MutexGuard,
+{
+    fn lock_guard(&mut self) -> impl StableDeref<Target = Self::Data> + '_ {
+        L::lock_guard(self)
+    }
 }
 
 // A RefCell is a lock in single threaded applications
@@ -182,90 +487,941 @@ impl<T> Mutex for &'_ RefCell<T> {
     }
 }
 
-#[cfg(test)]
-mod tests {
-    #![allow(dead_code)]
-    use crate::*;
+impl<T> MutexGuard for &'_ RefCell<T> {
+    fn lock_guard(&mut self) -> impl StableDeref<Target = T> + '_ {
+        self.borrow_mut()
+    }
+}
 
-    fn compile_test_single_move(mut a: impl Mutex<Data = i32>) {
-        a.lock(|a| {
-            *a += 1;
-        });
+#[cfg(feature = "std")]
+extern crate std;
+
+// A `std::sync::Mutex` is a lock on platforms that have the standard library
+#[cfg(feature = "std")]
+impl<T> Mutex for std::sync::Mutex<T> {
+    type Data = T;
+
+    fn lock<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R {
+        let mut guard = std::sync::Mutex::lock(self).unwrap();
+        f(&mut guard)
     }
 
-    fn compile_test_single_reference(a: &mut impl Mutex<Data = i32>) {
-        a.lock(|a| {
-            *a += 1;
-        });
+    fn try_lock<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        // Mirror `lock`'s panic-on-poison behavior; only contention (`WouldBlock`) is reported
+        // as `None`, since a poisoned lock is a real fault, not mere unavailability.
+        let mut guard = match std::sync::Mutex::try_lock(self) {
+            Ok(guard) => guard,
+            Err(std::sync::TryLockError::WouldBlock) => return None,
+            Err(std::sync::TryLockError::Poisoned(err)) => panic!("{}", err),
+        };
+        Some(f(&mut guard))
     }
+}
 
-    fn compile_test_double_move(mut a: impl Mutex<Data = i32>, mut b: impl Mutex<Data = i32>) {
-        a.lock(|a| {
-            *a += 1;
-        });
+#[cfg(feature = "std")]
+impl<T> MutexGuard for std::sync::Mutex<T> {
+    fn lock_guard(&mut self) -> impl StableDeref<Target = T> + '_ {
+        std::sync::Mutex::lock(self).unwrap()
+    }
+}
 
-        b.lock(|b| {
-            *b += 1;
-        });
+// A `critical_section::Mutex` wrapping a `RefCell` is a lock on any target that provides a
+// `critical-section` implementation
+#[cfg(feature = "critical-section")]
+impl<T> Mutex for critical_section::Mutex<RefCell<T>> {
+    type Data = T;
 
-        (a, b).lock(|a, b| {
-            *a += 1;
-            *b += 1;
-        });
+    fn lock<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R {
+        critical_section::with(|cs| f(&mut self.borrow(cs).borrow_mut()))
     }
+}
 
-    fn compile_test_double_reference(
-        a: &mut impl Mutex<Data = i32>,
-        b: &mut impl Mutex<Data = i32>,
-    ) {
-        a.lock(|a| {
-            *a += 1;
-        });
+#[cfg(feature = "critical-section")]
+impl<T> MutexGuard for critical_section::Mutex<RefCell<T>> {
+    fn lock_guard(&mut self) -> impl StableDeref<Target = T> + '_ {
+        CriticalSectionGuard::new(self)
+    }
+}
 
-        b.lock(|b| {
-            *b += 1;
-        });
+// RAII guard for `critical_section::Mutex`, holding the critical section open for its lifetime.
+//
+// The inner `RefMut` is what actually makes this sound: it goes through `RefCell`'s runtime
+// borrow flag, so two guards taken from the same mutex (e.g. via two `&self` calls to
+// `SharedMutex::lock_guard`) cannot both reach a live `&mut T`, as they could if every
+// `deref`/`deref_mut` call read the cell's raw pointer directly.
+#[cfg(feature = "critical-section")]
+struct CriticalSectionGuard<'a, T> {
+    guard: core::mem::ManuallyDrop<core::cell::RefMut<'a, T>>,
+    token: critical_section::RestoreState,
+}
 
-        (a, b).lock(|a, b| {
-            *a += 1;
-            *b += 1;
-        });
+#[cfg(feature = "critical-section")]
+impl<'a, T> CriticalSectionGuard<'a, T> {
+    fn new(mutex: &'a critical_section::Mutex<RefCell<T>>) -> Self {
+        let token = unsafe { critical_section::acquire() };
+        // Safety: `token` keeps the critical section open until `Drop::drop` releases it below,
+        // so a `CriticalSection<'a>` is a faithful witness of the section actually lasting `'a`.
+        let cs = unsafe { critical_section::CriticalSection::<'a>::new() };
+        let guard = core::mem::ManuallyDrop::new(mutex.borrow_ref_mut(cs));
+        CriticalSectionGuard { guard, token }
     }
+}
 
-    fn compile_test_move_and_reference(
-        mut a: impl Mutex<Data = i32>,
-        b: &mut impl Mutex<Data = i32>,
-    ) {
-        a.lock(|a| {
-            *a += 1;
-        });
+#[cfg(feature = "critical-section")]
+impl<'a, T> Deref for CriticalSectionGuard<'a, T> {
+    type Target = T;
 
-        b.lock(|b| {
-            *b += 1;
-        });
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
 
-        (a, b).lock(|a, b| {
-            *a += 1;
-            *b += 1;
-        });
+#[cfg(feature = "critical-section")]
+impl<'a, T> DerefMut for CriticalSectionGuard<'a, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
     }
+}
 
-    #[test]
-    fn refcell_lock() {
-        let a = core::cell::RefCell::new(0);
-        let b = core::cell::RefCell::new(0);
+#[cfg(feature = "critical-section")]
+impl<'a, T> Drop for CriticalSectionGuard<'a, T> {
+    fn drop(&mut self) {
+        // Drop the `RefMut` (clearing the borrow flag) before releasing the critical section, so
+        // no interrupt handler can observe the cell as still mutably borrowed once re-entrant.
+        unsafe { core::mem::ManuallyDrop::drop(&mut self.guard) };
+        unsafe { critical_section::release(self.token) }
+    }
+}
 
-        (&a).lock(|a| {
-            *a += 1;
-        });
+// A `spin::Mutex` is a lock usable on any target, backed by a spinlock
+#[cfg(feature = "spin")]
+impl<T> Mutex for spin::Mutex<T> {
+    type Data = T;
 
-        (&b).lock(|b| {
-            *b += 1;
-        });
+    fn lock<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R {
+        let mut guard = spin::Mutex::lock(self);
+        f(&mut guard)
+    }
 
-        (&a, &b).lock(|a,b| {
-            *a += 1;
-            *b += 1;
+    fn try_lock<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        let mut guard = spin::Mutex::try_lock(self)?;
+        Some(f(&mut guard))
+    }
+}
+
+#[cfg(feature = "spin")]
+impl<T> MutexGuard for spin::Mutex<T> {
+    fn lock_guard(&mut self) -> impl StableDeref<Target = T> + '_ {
+        spin::Mutex::lock(self)
+    }
+}
+
+/// Any object implementing this trait guarantees shared (read) access to the data contained
+/// within the lock, or exclusive (write) access when taken mutably.
+pub trait RwLock {
+    /// Data protected by the lock
+    type Data;
+
+    /// Creates a critical section and grants temporary shared access to the protected data
+    fn read<R>(&self, f: impl FnOnce(&Self::Data) -> R) -> R;
+
+    /// Creates a critical section and grants temporary exclusive access to the protected data
+    fn write<R>(&mut self, f: impl FnOnce(&mut Self::Data) -> R) -> R;
+}
+
+// `read`/`write` will now work on any mutable reference to a lock
+impl<L> RwLock for &'_ mut L
+where
+    L: RwLock,
+{
+    type Data = L::Data;
+
+    fn read<R>(&self, f: impl FnOnce(&Self::Data) -> R) -> R {
+        L::read(self, f)
+    }
+
+    fn write<R>(&mut self, f: impl FnOnce(&mut Self::Data) -> R) -> R {
+        L::write(self, f)
+    }
+}
+
+// A RefCell is a lock in single threaded applications
+impl<T> RwLock for &'_ RefCell<T> {
+    type Data = T;
+
+    fn read<R>(&self, f: impl FnOnce(&T) -> R) -> R {
+        f(&self.borrow())
+    }
+
+    fn write<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut self.borrow_mut())
+    }
+}
+
+/// Any object implementing this trait guarantees exclusive access to the data contained within
+/// the mutex through a shared `&self` reference. Implementors rely on interior mutability so the
+/// same lock can be shared behind several `&` references (e.g. a `static` or an `Arc`) and locked
+/// from multiple call sites.
+pub trait SharedMutex {
+    /// Data protected by the mutex
+    type Data;
+
+    /// Creates a critical section and grants temporary exclusive access to the protected data
+    fn lock<R>(&self, f: impl FnOnce(&mut Self::Data) -> R) -> R;
+
+    /// Locks the mutex and returns a RAII guard granting scoped access to the protected data.
+    /// The lock is released when the guard is dropped.
+    fn lock_guard(&self) -> impl StableDeref<Target = Self::Data> + '_;
+}
+
+// `lock`/`lock_guard` will now work on any shared reference to a lock, so the same `SharedMutex`
+// can be shared behind several `&` references and locked from each of them independently
+impl<L> SharedMutex for &'_ L
+where
+    L: SharedMutex,
+{
+    type Data = L::Data;
+
+    fn lock<R>(&self, f: impl FnOnce(&mut Self::Data) -> R) -> R {
+        L::lock(self, f)
+    }
+
+    fn lock_guard(&self) -> impl StableDeref<Target = Self::Data> + '_ {
+        L::lock_guard(self)
+    }
+}
+
+// A RefCell is a lock in single threaded applications
+impl<T> SharedMutex for RefCell<T> {
+    type Data = T;
+
+    fn lock<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        f(&mut self.borrow_mut())
+    }
+
+    fn lock_guard(&self) -> impl StableDeref<Target = T> + '_ {
+        self.borrow_mut()
+    }
+}
+
+// A `std::sync::Mutex` is natively locked through `&self`, so it can be shared behind a
+// `static`/`Arc` and locked from multiple call sites without ever needing `&mut` access to it
+#[cfg(feature = "std")]
+impl<T> SharedMutex for std::sync::Mutex<T> {
+    type Data = T;
+
+    fn lock<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let mut guard = std::sync::Mutex::lock(self).unwrap();
+        f(&mut guard)
+    }
+
+    fn lock_guard(&self) -> impl StableDeref<Target = T> + '_ {
+        std::sync::Mutex::lock(self).unwrap()
+    }
+}
+
+// A `critical_section::Mutex` wrapping a `RefCell` is natively locked through `&self`
+#[cfg(feature = "critical-section")]
+impl<T> SharedMutex for critical_section::Mutex<RefCell<T>> {
+    type Data = T;
+
+    fn lock<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        critical_section::with(|cs| f(&mut self.borrow(cs).borrow_mut()))
+    }
+
+    fn lock_guard(&self) -> impl StableDeref<Target = T> + '_ {
+        CriticalSectionGuard::new(self)
+    }
+}
+
+// A `spin::Mutex` is natively locked through `&self`
+#[cfg(feature = "spin")]
+impl<T> SharedMutex for spin::Mutex<T> {
+    type Data = T;
+
+    fn lock<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let mut guard = spin::Mutex::lock(self);
+        f(&mut guard)
+    }
+
+    fn lock_guard(&self) -> impl StableDeref<Target = T> + '_ {
+        spin::Mutex::lock(self)
+    }
+}
+
+/// Adapts any [`SharedMutex`] to the [`Mutex`] interface so a single lock stored behind a shared
+/// reference (e.g. in a `static` or an `Arc`) can still be handed to code generic over `Mutex`.
+///
+/// Coherence rules out a blanket `impl<L: SharedMutex> Mutex for L`, since a concrete type could
+/// implement both traits directly and the impls would conflict. Callers therefore have to wrap
+/// the reference explicitly, e.g. `some_generic_fn(Shared(&lock))` rather than `some_generic_fn(&lock)`.
+pub struct Shared<'a, L: ?Sized>(pub &'a L);
+
+impl<'a, L> Mutex for Shared<'a, L>
+where
+    L: SharedMutex,
+{
+    type Data = L::Data;
+
+    fn lock<R>(&mut self, f: impl FnOnce(&mut Self::Data) -> R) -> R {
+        self.0.lock(f)
+    }
+}
+
+impl<'a, L> MutexGuard for Shared<'a, L>
+where
+    L: SharedMutex,
+{
+    fn lock_guard(&mut self) -> impl StableDeref<Target = Self::Data> + '_ {
+        self.0.lock_guard()
+    }
+}
+
+/// Shim trait used by [`map`] so the projection closure can be bounded with a `for<'a>` clause
+/// that names both the input and output lifetimes, which a plain `FnOnce(&mut Arg) -> &mut U`
+/// bound cannot express. This mirrors the technique used by `parking_lot`'s mapped guards.
+pub trait LockMap<'a, Arg: ?Sized> {
+    /// The value produced by the projection
+    type Output;
+
+    /// Applies the projection to the borrowed data
+    fn call(self, arg: &'a mut Arg) -> Self::Output;
+}
+
+impl<'a, Arg, U, F> LockMap<'a, Arg> for F
+where
+    Arg: 'a,
+    U: ?Sized + 'a,
+    F: FnOnce(&'a mut Arg) -> &'a mut U,
+{
+    type Output = &'a mut U;
+
+    fn call(self, arg: &'a mut Arg) -> Self::Output {
+        self(arg)
+    }
+}
+
+/// Marker for guards whose `Deref`/`DerefMut` target lives at a fixed address in storage owned
+/// independently of the guard value itself (typically reached through a reference or pointer
+/// held inside the guard), so the target's address does not change when the guard is moved.
+///
+/// [`map`] relies on this: it borrows `*guard` to compute a pointer into the target, then moves
+/// `guard` into the returned [`MappedGuard`]. A move is a bitwise relocation that does not fix up
+/// previously-taken raw pointers, so mapping a guard whose target lives inline (e.g. a bare
+/// `struct Guard(T)` deref'ing to `&mut self.0`) would leave that pointer dangling into the
+/// guard's old stack slot. Implementing this trait for such a guard is unsound.
+///
+/// # Safety
+///
+/// Implementors must guarantee that the reference returned by `DerefMut::deref_mut` remains
+/// valid at the same address regardless of where the guard itself is subsequently moved to.
+pub unsafe trait StableDeref: DerefMut {}
+
+unsafe impl<'a, T: ?Sized> StableDeref for core::cell::RefMut<'a, T> {}
+
+#[cfg(feature = "std")]
+unsafe impl<'a, T: ?Sized> StableDeref for std::sync::MutexGuard<'a, T> {}
+
+#[cfg(feature = "critical-section")]
+unsafe impl<'a, T> StableDeref for CriticalSectionGuard<'a, T> {}
+
+#[cfg(feature = "spin")]
+unsafe impl<'a, T: ?Sized> StableDeref for spin::MutexGuard<'a, T> {}
+
+/// A guard produced by [`map`], projecting a locked guard `G` onto a sub-part `U` of its data
+/// while keeping the original lock held for as long as the mapped guard is alive.
+pub struct MappedGuard<G, U: ?Sized> {
+    // Kept only so the original lock is released when the mapped guard is dropped; the
+    // projected data is accessed exclusively through `data` below.
+    #[allow(dead_code)]
+    guard: G,
+    data: *mut U,
+}
+
+impl<G, U: ?Sized> Deref for MappedGuard<G, U> {
+    type Target = U;
+
+    fn deref(&self) -> &U {
+        // Safe because `data` was derived from a `&mut U` borrowed out of `guard` while `guard`
+        // still lived at the address `map` computed it from. `G: StableDeref` (checked when the
+        // `MappedGuard` was built) guarantees that address doesn't change when `guard` is moved
+        // into this struct, and `guard` is otherwise never accessed for as long as this exists.
+        unsafe { &*self.data }
+    }
+}
+
+impl<G, U: ?Sized> DerefMut for MappedGuard<G, U> {
+    fn deref_mut(&mut self) -> &mut U {
+        // Safety: see `Deref::deref` above.
+        unsafe { &mut *self.data }
+    }
+}
+
+// The raw pointer only ever points at data reachable through `guard`, so `MappedGuard` is safe
+// to send/share across threads whenever `guard` and the projected data themselves are.
+unsafe impl<G: Send, U: ?Sized + Send> Send for MappedGuard<G, U> {}
+unsafe impl<G: Sync, U: ?Sized + Sync> Sync for MappedGuard<G, U> {}
+
+// A `MappedGuard`'s own target address is fixed in `data` at construction time and never moves
+// relative to `self`, so it can itself be mapped again regardless of the inner `G`.
+unsafe impl<G, U: ?Sized> StableDeref for MappedGuard<G, U> {}
+
+/// Projects a lock guard onto a sub-field of its data, keeping the lock held for as long as the
+/// returned guard is alive.
+///
+/// Only available for guards that implement [`StableDeref`] (the guards returned by this
+/// crate's own `lock_guard` implementations all qualify); see that trait for why a plain
+/// `DerefMut` bound would be unsound here.
+///
+/// ```
+/// use mutex_trait::*;
+/// use core::cell::RefCell;
+///
+/// struct Pair {
+///     a: i32,
+///     b: i32,
+/// }
+///
+/// let mut lock = RefCell::new(Pair { a: 1, b: 2 });
+/// fn project(pair: &mut Pair) -> &mut i32 {
+///     &mut pair.a
+/// }
+///
+/// let mut guard = mutex_trait::map(lock.lock_guard(), project);
+/// *guard += 1;
+/// drop(guard);
+/// assert_eq!(lock.borrow().a, 2);
+/// ```
+pub fn map<G, Arg, U: ?Sized, F>(mut guard: G, f: F) -> MappedGuard<G, U>
+where
+    G: StableDeref<Target = Arg>,
+    F: for<'b> LockMap<'b, Arg, Output = &'b mut U>,
+{
+    let data: *mut U = f.call(&mut *guard);
+    MappedGuard { guard, data }
+}
+
+/// Shim trait used by [`map_owned`], analogous to [`LockMap`] but for projections that compute
+/// an owned value from the locked data instead of borrowing a sub-field of it. Kept as a
+/// separate trait (rather than a second blanket impl on [`LockMap`]) because `F: FnOnce(&'a mut
+/// Arg) -> U` for unconstrained `U` would overlap with `LockMap`'s existing `U = &'a mut _` impl.
+pub trait OwnedLockMap<'a, Arg: ?Sized> {
+    /// The value produced by the projection
+    type Output;
+
+    /// Applies the projection to the borrowed data
+    fn call(self, arg: &'a mut Arg) -> Self::Output;
+}
+
+impl<'a, Arg, V, F> OwnedLockMap<'a, Arg> for F
+where
+    Arg: 'a,
+    F: FnOnce(&'a mut Arg) -> V,
+{
+    type Output = V;
+
+    fn call(self, arg: &'a mut Arg) -> Self::Output {
+        self(arg)
+    }
+}
+
+/// A guard produced by [`map_owned`], holding a value computed from a lock's data while keeping
+/// the original lock held for as long as the guard is alive.
+pub struct OwnedMappedGuard<G, V> {
+    // Kept only so the original lock is released when the guard is dropped.
+    #[allow(dead_code)]
+    guard: G,
+    value: V,
+}
+
+impl<G, V> Deref for OwnedMappedGuard<G, V> {
+    type Target = V;
+
+    fn deref(&self) -> &V {
+        &self.value
+    }
+}
+
+impl<G, V> DerefMut for OwnedMappedGuard<G, V> {
+    fn deref_mut(&mut self) -> &mut V {
+        &mut self.value
+    }
+}
+
+/// Projects a lock guard onto an owned value computed from its data, keeping the lock held for
+/// as long as the returned guard is alive. Unlike [`map`], the projection doesn't need to borrow
+/// back into the original data, so any `G: DerefMut` works, not just [`StableDeref`] guards.
+///
+/// ```
+/// use mutex_trait::*;
+/// use core::cell::RefCell;
+///
+/// struct Pair {
+///     a: i32,
+///     b: i32,
+/// }
+///
+/// let mut lock = RefCell::new(Pair { a: 1, b: 2 });
+/// fn sum(pair: &mut Pair) -> i32 {
+///     pair.a + pair.b
+/// }
+///
+/// let guard = mutex_trait::map_owned(lock.lock_guard(), sum);
+/// assert_eq!(*guard, 3);
+/// ```
+pub fn map_owned<G, Arg, V, F>(mut guard: G, f: F) -> OwnedMappedGuard<G, V>
+where
+    G: DerefMut<Target = Arg>,
+    F: for<'b> OwnedLockMap<'b, Arg, Output = V>,
+{
+    let value = f.call(&mut *guard);
+    OwnedMappedGuard { guard, value }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(dead_code)]
+    use crate::*;
+
+    fn compile_test_single_move(mut a: impl Mutex<Data = i32>) {
+        a.lock(|a| {
+            *a += 1;
+        });
+    }
+
+    fn compile_test_single_reference(a: &mut impl Mutex<Data = i32>) {
+        a.lock(|a| {
+            *a += 1;
+        });
+    }
+
+    fn compile_test_double_move(mut a: impl Mutex<Data = i32>, mut b: impl Mutex<Data = i32>) {
+        a.lock(|a| {
+            *a += 1;
+        });
+
+        b.lock(|b| {
+            *b += 1;
+        });
+
+        (a, b).lock(|a, b| {
+            *a += 1;
+            *b += 1;
+        });
+    }
+
+    fn compile_test_double_reference(
+        a: &mut impl Mutex<Data = i32>,
+        b: &mut impl Mutex<Data = i32>,
+    ) {
+        a.lock(|a| {
+            *a += 1;
+        });
+
+        b.lock(|b| {
+            *b += 1;
+        });
+
+        (a, b).lock(|a, b| {
+            *a += 1;
+            *b += 1;
+        });
+    }
+
+    fn compile_test_move_and_reference(
+        mut a: impl Mutex<Data = i32>,
+        b: &mut impl Mutex<Data = i32>,
+    ) {
+        a.lock(|a| {
+            *a += 1;
+        });
+
+        b.lock(|b| {
+            *b += 1;
+        });
+
+        (a, b).lock(|a, b| {
+            *a += 1;
+            *b += 1;
+        });
+    }
+
+    #[test]
+    // Calling through the traits exercises the `Mutex for &RefCell<T>` impl specifically;
+    // method-call syntax (`a.lock(..)`, `(&a).lock(..)`, `(&a, &b).lock(..)`) would resolve to
+    // `SharedMutex for RefCell<T>` instead and stop covering this impl.
+    fn refcell_lock() {
+        let a = core::cell::RefCell::new(0);
+        let b = core::cell::RefCell::new(0);
+
+        Mutex::lock(&mut &a, |a| {
+            *a += 1;
+        });
+
+        Mutex::lock(&mut &b, |b| {
+            *b += 1;
+        });
+
+        TupleExt02::lock(&mut (&a, &b), |a, b| {
+            *a += 1;
+            *b += 1;
+        });
+    }
+
+    #[test]
+    fn refcell_try_lock() {
+        let a = core::cell::RefCell::new(0);
+
+        let r = (&a).try_lock(|a| {
+            *a += 1;
+            *a
+        });
+
+        assert_eq!(r, Some(1));
+    }
+
+    #[test]
+    fn refcell_lock_guard() {
+        let a = core::cell::RefCell::new(0);
+
+        {
+            let mut guard = a.lock_guard();
+            *guard += 1;
+        }
+
+        assert_eq!(*a.borrow(), 1);
+    }
+
+    struct Pair {
+        a: i32,
+        b: i32,
+    }
+
+    #[test]
+    fn lock_guard_and_map() {
+        let lock = core::cell::RefCell::new(Pair { a: 1, b: 2 });
+
+        {
+            fn project(pair: &mut Pair) -> &mut i32 {
+                &mut pair.a
+            }
+
+            let mut guard = map(lock.lock_guard(), project);
+            *guard += 1;
+        }
+
+        assert_eq!(lock.borrow().a, 2);
+        assert_eq!(lock.borrow().b, 2);
+    }
+
+    #[test]
+    fn lock_guard_and_map_owned() {
+        let lock = core::cell::RefCell::new(Pair { a: 1, b: 2 });
+
+        fn sum(pair: &mut Pair) -> i32 {
+            pair.a + pair.b
+        }
+
+        let guard = map_owned(lock.lock_guard(), sum);
+        assert_eq!(*guard, 3);
+
+        // The lock is still held while the mapped guard is alive
+        assert!(lock.try_borrow_mut().is_err());
+        drop(guard);
+        assert!(lock.try_borrow_mut().is_ok());
+    }
+
+    /// A closure-only `Mutex`, like `cortex_m::interrupt::Mutex<RefCell<T>>`, that cannot
+    /// implement `MutexGuard` because it never holds the critical section open past `lock`'s
+    /// closure. Exercises that `MutexGuard` being a separate trait doesn't force this impl.
+    struct ClosureOnlyMutex<T> {
+        data: core::cell::RefCell<T>,
+    }
+
+    impl<T> Mutex for ClosureOnlyMutex<T> {
+        type Data = T;
+
+        fn lock<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R {
+            f(&mut self.data.borrow_mut())
+        }
+    }
+
+    fn compile_test_closure_only_mutex(mut a: impl Mutex<Data = i32>) {
+        a.lock(|a| {
+            *a += 1;
+        });
+    }
+
+    #[test]
+    fn closure_only_mutex_lock() {
+        let mut a = ClosureOnlyMutex {
+            data: core::cell::RefCell::new(0),
+        };
+
+        a.lock(|a| {
+            *a += 1;
+        });
+
+        compile_test_closure_only_mutex(a);
+    }
+
+    /// A `Mutex` whose `try_lock` can be made to fail on demand, used to exercise the
+    /// all-or-nothing tuple `try_lock` semantics.
+    struct FlakyMutex<T> {
+        data: core::cell::RefCell<T>,
+        fail: bool,
+    }
+
+    impl<T> Mutex for FlakyMutex<T> {
+        type Data = T;
+
+        fn lock<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> R {
+            f(&mut self.data.borrow_mut())
+        }
+
+        fn try_lock<R>(&mut self, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+            if self.fail {
+                None
+            } else {
+                Some(f(&mut self.data.borrow_mut()))
+            }
+        }
+    }
+
+    impl<T> MutexGuard for FlakyMutex<T> {
+        fn lock_guard(&mut self) -> impl StableDeref<Target = T> + '_ {
+            self.data.borrow_mut()
+        }
+    }
+
+    #[test]
+    fn try_lock_all_or_nothing() {
+        let mut a = FlakyMutex {
+            data: core::cell::RefCell::new(1),
+            fail: false,
+        };
+        let mut b = FlakyMutex {
+            data: core::cell::RefCell::new(2),
+            fail: true,
+        };
+
+        let result = (&mut a, &mut b).try_lock(|a, b| {
+            *a += 1;
+            *b += 1;
+        });
+
+        assert_eq!(result, None);
+        // `b` failed to lock before `f` ran, so `a` must be left untouched
+        assert_eq!(*a.data.borrow(), 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn std_mutex_lock() {
+        let mut a = std::sync::Mutex::new(0);
+
+        // `std::sync::Mutex` has its own inherent `lock`, so the trait method needs disambiguation
+        Mutex::lock(&mut a, |a| {
+            *a += 1;
+        });
+
+        assert_eq!(*a.lock().unwrap(), 1);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn std_mutex_shared_lock() {
+        let a = std::sync::Mutex::new(0);
+
+        // Locked through two separate shared references, as in a `static`/`Arc`
+        let r1 = &a;
+        let r2 = &a;
+        SharedMutex::lock(r1, |a| {
+            *a += 1;
+        });
+        SharedMutex::lock(r2, |a| {
+            *a += 1;
+        });
+
+        assert_eq!(*a.lock().unwrap(), 2);
+    }
+
+    #[cfg(feature = "critical-section")]
+    #[test]
+    fn critical_section_mutex_lock() {
+        let mut a = critical_section::Mutex::new(core::cell::RefCell::new(0));
+
+        // Now that `SharedMutex` is also implemented for this type, disambiguate to
+        // exercise the `&mut self`-based `Mutex` impl specifically.
+        Mutex::lock(&mut a, |a| {
+            *a += 1;
+        });
+
+        critical_section::with(|cs| {
+            assert_eq!(*a.borrow(cs).borrow(), 1);
+        });
+    }
+
+    #[cfg(feature = "critical-section")]
+    #[test]
+    fn critical_section_mutex_shared_lock() {
+        let a = critical_section::Mutex::new(core::cell::RefCell::new(0));
+
+        let r1 = &a;
+        let r2 = &a;
+        SharedMutex::lock(r1, |a| {
+            *a += 1;
+        });
+        SharedMutex::lock(r2, |a| {
+            *a += 1;
+        });
+
+        critical_section::with(|cs| {
+            assert_eq!(*a.borrow(cs).borrow(), 2);
+        });
+    }
+
+    #[cfg(feature = "critical-section")]
+    #[test]
+    fn critical_section_shared_lock_guard_is_exclusive() {
+        let a = critical_section::Mutex::new(core::cell::RefCell::new(0));
+
+        let guard = SharedMutex::lock_guard(&a);
+
+        // The guard holds a real `RefMut`, so the `RefCell`'s runtime borrow flag rejects a
+        // second mutable borrow for as long as the guard is alive, instead of handing out a
+        // second aliasing `&mut` into the same cell.
+        critical_section::with(|cs| {
+            assert!(a.borrow(cs).try_borrow_mut().is_err());
+        });
+
+        drop(guard);
+
+        critical_section::with(|cs| {
+            assert!(a.borrow(cs).try_borrow_mut().is_ok());
+        });
+    }
+
+    #[cfg(feature = "spin")]
+    #[test]
+    fn spin_mutex_lock() {
+        let mut a = spin::Mutex::new(0);
+
+        // `spin::Mutex` has its own inherent `lock`, so the trait method needs disambiguation
+        Mutex::lock(&mut a, |a| {
+            *a += 1;
+        });
+
+        assert_eq!(*a.lock(), 1);
+    }
+
+    #[cfg(feature = "spin")]
+    #[test]
+    fn spin_mutex_shared_lock() {
+        let a = spin::Mutex::new(0);
+
+        let r1 = &a;
+        let r2 = &a;
+        SharedMutex::lock(r1, |a| {
+            *a += 1;
+        });
+        SharedMutex::lock(r2, |a| {
+            *a += 1;
+        });
+
+        assert_eq!(*a.lock(), 2);
+    }
+
+    fn compile_test_rwlock_read(
+        a: &mut impl RwLock<Data = i32>,
+        b: &mut impl RwLock<Data = i32>,
+    ) {
+        a.read(|a| {
+            let _ = *a;
+        });
+
+        (a, b).read(|a, b| {
+            let _ = *a + *b;
+        });
+    }
+
+    fn compile_test_rwlock_write(
+        a: &mut impl RwLock<Data = i32>,
+        b: &mut impl RwLock<Data = i32>,
+    ) {
+        a.write(|a| {
+            *a += 1;
+        });
+
+        (a, b).write(|a, b| {
+            *a += 1;
+            *b += 1;
+        });
+    }
+
+    #[test]
+    fn refcell_rwlock() {
+        let a = core::cell::RefCell::new(0);
+        let b = core::cell::RefCell::new(0);
+
+        (&a).write(|a| {
+            *a += 1;
+        });
+
+        (&a).read(|a| {
+            assert_eq!(*a, 1);
+        });
+
+        (&a, &b).write(|a, b| {
+            *a += 1;
+            *b += 1;
+        });
+
+        (&a, &b).read(|a, b| {
+            assert_eq!(*a, 2);
+            assert_eq!(*b, 1);
+        });
+    }
+
+    fn compile_test_shared_lock(a: &impl SharedMutex<Data = i32>, b: &impl SharedMutex<Data = i32>) {
+        a.lock(|a| {
+            *a += 1;
+        });
+
+        (a, b).lock(|a, b| {
+            *a += 1;
+            *b += 1;
+        });
+    }
+
+    #[test]
+    fn refcell_shared_lock() {
+        let a = core::cell::RefCell::new(0);
+        let b = core::cell::RefCell::new(0);
+
+        // Lock through two separate shared references to the same mutex
+        let r1 = &a;
+        let r2 = &a;
+        r1.lock(|a| {
+            *a += 1;
+        });
+        r2.lock(|a| {
+            *a += 1;
+        });
+
+        (&a, &b).lock(|a, b| {
+            *a += 1;
+            *b += 1;
+        });
+
+        assert_eq!(*a.borrow(), 3);
+        assert_eq!(*b.borrow(), 1);
+    }
+
+    fn compile_test_shared_via_mutex(a: impl SharedMutex<Data = i32>) {
+        let mut bridged = Shared(&a);
+        bridged.lock(|a| {
+            *a += 1;
         });
     }
 }